@@ -0,0 +1,367 @@
+use std::collections::HashSet;
+
+const BLOCK_SIZE: usize = 16;
+const NK: usize = 4;
+const NR: usize = 10;
+
+#[rustfmt::skip]
+const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+#[rustfmt::skip]
+const INV_SBOX: [u8; 256] = [
+    0x52, 0x09, 0x6a, 0xd5, 0x30, 0x36, 0xa5, 0x38, 0xbf, 0x40, 0xa3, 0x9e, 0x81, 0xf3, 0xd7, 0xfb,
+    0x7c, 0xe3, 0x39, 0x82, 0x9b, 0x2f, 0xff, 0x87, 0x34, 0x8e, 0x43, 0x44, 0xc4, 0xde, 0xe9, 0xcb,
+    0x54, 0x7b, 0x94, 0x32, 0xa6, 0xc2, 0x23, 0x3d, 0xee, 0x4c, 0x95, 0x0b, 0x42, 0xfa, 0xc3, 0x4e,
+    0x08, 0x2e, 0xa1, 0x66, 0x28, 0xd9, 0x24, 0xb2, 0x76, 0x5b, 0xa2, 0x49, 0x6d, 0x8b, 0xd1, 0x25,
+    0x72, 0xf8, 0xf6, 0x64, 0x86, 0x68, 0x98, 0x16, 0xd4, 0xa4, 0x5c, 0xcc, 0x5d, 0x65, 0xb6, 0x92,
+    0x6c, 0x70, 0x48, 0x50, 0xfd, 0xed, 0xb9, 0xda, 0x5e, 0x15, 0x46, 0x57, 0xa7, 0x8d, 0x9d, 0x84,
+    0x90, 0xd8, 0xab, 0x00, 0x8c, 0xbc, 0xd3, 0x0a, 0xf7, 0xe4, 0x58, 0x05, 0xb8, 0xb3, 0x45, 0x06,
+    0xd0, 0x2c, 0x1e, 0x8f, 0xca, 0x3f, 0x0f, 0x02, 0xc1, 0xaf, 0xbd, 0x03, 0x01, 0x13, 0x8a, 0x6b,
+    0x3a, 0x91, 0x11, 0x41, 0x4f, 0x67, 0xdc, 0xea, 0x97, 0xf2, 0xcf, 0xce, 0xf0, 0xb4, 0xe6, 0x73,
+    0x96, 0xac, 0x74, 0x22, 0xe7, 0xad, 0x35, 0x85, 0xe2, 0xf9, 0x37, 0xe8, 0x1c, 0x75, 0xdf, 0x6e,
+    0x47, 0xf1, 0x1a, 0x71, 0x1d, 0x29, 0xc5, 0x89, 0x6f, 0xb7, 0x62, 0x0e, 0xaa, 0x18, 0xbe, 0x1b,
+    0xfc, 0x56, 0x3e, 0x4b, 0xc6, 0xd2, 0x79, 0x20, 0x9a, 0xdb, 0xc0, 0xfe, 0x78, 0xcd, 0x5a, 0xf4,
+    0x1f, 0xdd, 0xa8, 0x33, 0x88, 0x07, 0xc7, 0x31, 0xb1, 0x12, 0x10, 0x59, 0x27, 0x80, 0xec, 0x5f,
+    0x60, 0x51, 0x7f, 0xa9, 0x19, 0xb5, 0x4a, 0x0d, 0x2d, 0xe5, 0x7a, 0x9f, 0x93, 0xc9, 0x9c, 0xef,
+    0xa0, 0xe0, 0x3b, 0x4d, 0xae, 0x2a, 0xf5, 0xb0, 0xc8, 0xeb, 0xbb, 0x3c, 0x83, 0x53, 0x99, 0x61,
+    0x17, 0x2b, 0x04, 0x7e, 0xba, 0x77, 0xd6, 0x26, 0xe1, 0x69, 0x14, 0x63, 0x55, 0x21, 0x0c, 0x7d,
+];
+
+const RCON: [u8; NR] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+fn xtime(b: u8) -> u8 {
+    if b & 0x80 != 0 {
+        (b << 1) ^ 0x1b
+    } else {
+        b << 1
+    }
+}
+
+fn gmul(mut a: u8, mut b: u8) -> u8 {
+    let mut p = 0;
+
+    for _ in 0..8 {
+        if b & 1 != 0 {
+            p ^= a;
+        }
+        a = xtime(a);
+        b >>= 1;
+    }
+
+    p
+}
+
+type KeySchedule = [[u8; 4]; 4 * (NR + 1)];
+
+fn key_expansion(key: [u8; 16]) -> KeySchedule {
+    let mut words = [[0u8; 4]; 4 * (NR + 1)];
+
+    for i in 0..NK {
+        words[i] = key[4 * i..4 * i + 4].try_into().unwrap();
+    }
+
+    for i in NK..words.len() {
+        let mut temp = words[i - 1];
+
+        if i % NK == 0 {
+            temp = [temp[1], temp[2], temp[3], temp[0]].map(|b| SBOX[b as usize]);
+            temp[0] ^= RCON[i / NK - 1];
+        }
+
+        words[i] = [
+            words[i - NK][0] ^ temp[0],
+            words[i - NK][1] ^ temp[1],
+            words[i - NK][2] ^ temp[2],
+            words[i - NK][3] ^ temp[3],
+        ];
+    }
+
+    words
+}
+
+fn add_round_key(state: &mut [u8; 16], schedule: &KeySchedule, round: usize) {
+    for c in 0..4 {
+        for r in 0..4 {
+            state[4 * c + r] ^= schedule[4 * round + c][r];
+        }
+    }
+}
+
+fn sub_bytes(state: &mut [u8; 16]) {
+    for b in state.iter_mut() {
+        *b = SBOX[*b as usize];
+    }
+}
+
+fn inv_sub_bytes(state: &mut [u8; 16]) {
+    for b in state.iter_mut() {
+        *b = INV_SBOX[*b as usize];
+    }
+}
+
+fn shift_rows(state: &mut [u8; 16]) {
+    let s = *state;
+    for r in 1..4 {
+        for c in 0..4 {
+            state[4 * c + r] = s[4 * ((c + r) % 4) + r];
+        }
+    }
+}
+
+fn inv_shift_rows(state: &mut [u8; 16]) {
+    let s = *state;
+    for r in 1..4 {
+        for c in 0..4 {
+            state[4 * c + r] = s[4 * ((c + 4 - r) % 4) + r];
+        }
+    }
+}
+
+fn mix_columns(state: &mut [u8; 16]) {
+    for c in 0..4 {
+        let col: [u8; 4] = state[4 * c..4 * c + 4].try_into().unwrap();
+
+        state[4 * c] = gmul(col[0], 2) ^ gmul(col[1], 3) ^ col[2] ^ col[3];
+        state[4 * c + 1] = col[0] ^ gmul(col[1], 2) ^ gmul(col[2], 3) ^ col[3];
+        state[4 * c + 2] = col[0] ^ col[1] ^ gmul(col[2], 2) ^ gmul(col[3], 3);
+        state[4 * c + 3] = gmul(col[0], 3) ^ col[1] ^ col[2] ^ gmul(col[3], 2);
+    }
+}
+
+fn inv_mix_columns(state: &mut [u8; 16]) {
+    for c in 0..4 {
+        let col: [u8; 4] = state[4 * c..4 * c + 4].try_into().unwrap();
+
+        state[4 * c] = gmul(col[0], 14) ^ gmul(col[1], 11) ^ gmul(col[2], 13) ^ gmul(col[3], 9);
+        state[4 * c + 1] = gmul(col[0], 9) ^ gmul(col[1], 14) ^ gmul(col[2], 11) ^ gmul(col[3], 13);
+        state[4 * c + 2] = gmul(col[0], 13) ^ gmul(col[1], 9) ^ gmul(col[2], 14) ^ gmul(col[3], 11);
+        state[4 * c + 3] = gmul(col[0], 11) ^ gmul(col[1], 13) ^ gmul(col[2], 9) ^ gmul(col[3], 14);
+    }
+}
+
+fn encrypt_block(schedule: &KeySchedule, block: [u8; 16]) -> [u8; 16] {
+    let mut state = block;
+
+    add_round_key(&mut state, schedule, 0);
+
+    for round in 1..NR {
+        sub_bytes(&mut state);
+        shift_rows(&mut state);
+        mix_columns(&mut state);
+        add_round_key(&mut state, schedule, round);
+    }
+
+    sub_bytes(&mut state);
+    shift_rows(&mut state);
+    add_round_key(&mut state, schedule, NR);
+
+    state
+}
+
+fn decrypt_block(schedule: &KeySchedule, block: [u8; 16]) -> [u8; 16] {
+    let mut state = block;
+
+    add_round_key(&mut state, schedule, NR);
+
+    for round in (1..NR).rev() {
+        inv_shift_rows(&mut state);
+        inv_sub_bytes(&mut state);
+        add_round_key(&mut state, schedule, round);
+        inv_mix_columns(&mut state);
+    }
+
+    inv_shift_rows(&mut state);
+    inv_sub_bytes(&mut state);
+    add_round_key(&mut state, schedule, 0);
+
+    state
+}
+
+fn pkcs7_pad(bytes: &[u8]) -> Vec<u8> {
+    let pad = BLOCK_SIZE - bytes.len() % BLOCK_SIZE;
+
+    let mut padded = Vec::with_capacity(bytes.len() + pad);
+    padded.extend_from_slice(bytes);
+    padded.extend(std::iter::repeat_n(pad as u8, pad));
+
+    padded
+}
+
+fn pkcs7_unpad(bytes: &[u8]) -> Result<&[u8], ()> {
+    let pad = *bytes.last().ok_or(())?;
+
+    if pad == 0 || pad as usize > bytes.len() {
+        return Err(());
+    }
+
+    if !bytes[bytes.len() - pad as usize..].iter().all(|&b| b == pad) {
+        return Err(());
+    }
+
+    Ok(&bytes[..bytes.len() - pad as usize])
+}
+
+pub fn encrypt_ecb(key: [u8; 16], bytes: &[u8]) -> Vec<u8> {
+    let schedule = key_expansion(key);
+    let padded = pkcs7_pad(bytes);
+
+    let mut out = Vec::with_capacity(padded.len());
+    for block in padded.chunks(BLOCK_SIZE) {
+        let block: [u8; 16] = block.try_into().unwrap();
+        out.extend(encrypt_block(&schedule, block));
+    }
+
+    out
+}
+
+pub fn decrypt_ecb(key: [u8; 16], bytes: &[u8]) -> Result<Vec<u8>, ()> {
+    if bytes.len() % BLOCK_SIZE != 0 || bytes.is_empty() {
+        return Err(());
+    }
+
+    let schedule = key_expansion(key);
+
+    let mut out = Vec::with_capacity(bytes.len());
+    for block in bytes.chunks(BLOCK_SIZE) {
+        let block: [u8; 16] = block.try_into().unwrap();
+        out.extend(decrypt_block(&schedule, block));
+    }
+
+    pkcs7_unpad(&out).map(<[u8]>::to_vec)
+}
+
+pub fn encrypt_cbc(key: [u8; 16], iv: [u8; 16], bytes: &[u8]) -> Vec<u8> {
+    let schedule = key_expansion(key);
+    let padded = pkcs7_pad(bytes);
+
+    let mut prev = iv;
+    let mut out = Vec::with_capacity(padded.len());
+    for block in padded.chunks(BLOCK_SIZE) {
+        let mut block: [u8; 16] = block.try_into().unwrap();
+        for (b, p) in block.iter_mut().zip(prev) {
+            *b ^= p;
+        }
+
+        let encrypted = encrypt_block(&schedule, block);
+        out.extend(encrypted);
+        prev = encrypted;
+    }
+
+    out
+}
+
+pub fn decrypt_cbc(key: [u8; 16], iv: [u8; 16], bytes: &[u8]) -> Result<Vec<u8>, ()> {
+    if bytes.len() % BLOCK_SIZE != 0 || bytes.is_empty() {
+        return Err(());
+    }
+
+    let schedule = key_expansion(key);
+
+    let mut prev = iv;
+    let mut out = Vec::with_capacity(bytes.len());
+    for block in bytes.chunks(BLOCK_SIZE) {
+        let block: [u8; 16] = block.try_into().unwrap();
+        let mut decrypted = decrypt_block(&schedule, block);
+        for (b, p) in decrypted.iter_mut().zip(prev) {
+            *b ^= p;
+        }
+
+        out.extend(decrypted);
+        prev = block;
+    }
+
+    pkcs7_unpad(&out).map(<[u8]>::to_vec)
+}
+
+/// Scores `bytes` by how many identical `block_size`-byte blocks it contains, which is
+/// the tell-tale sign of ECB mode leaking repeated plaintext blocks.
+pub fn detect_ecb(bytes: &[u8], block_size: usize) -> usize {
+    let mut seen = HashSet::new();
+    let mut duplicates = 0;
+
+    for block in bytes.chunks_exact(block_size) {
+        if !seen.insert(block) {
+            duplicates += 1;
+        }
+    }
+
+    duplicates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.197.pdf appendix B
+    const KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f,
+        0x3c,
+    ];
+    const PLAINTEXT: [u8; 16] = [
+        0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07,
+        0x34,
+    ];
+    const CIPHERTEXT: [u8; 16] = [
+        0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb, 0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a, 0x0b,
+        0x32,
+    ];
+
+    #[test]
+    fn encrypt_block_matches_fips_197() {
+        let schedule = key_expansion(KEY);
+        assert_eq!(encrypt_block(&schedule, PLAINTEXT), CIPHERTEXT);
+    }
+
+    #[test]
+    fn decrypt_block_matches_fips_197() {
+        let schedule = key_expansion(KEY);
+        assert_eq!(decrypt_block(&schedule, CIPHERTEXT), PLAINTEXT);
+    }
+
+    #[test]
+    fn ecb_round_trips() {
+        let message = b"YELLOW SUBMARINE, the rest of the story goes on for a while longer";
+
+        let encrypted = encrypt_ecb(KEY, message);
+        let decrypted = decrypt_ecb(KEY, &encrypted).unwrap();
+
+        assert_eq!(decrypted, message);
+    }
+
+    #[test]
+    fn cbc_round_trips() {
+        let message = b"YELLOW SUBMARINE, the rest of the story goes on for a while longer";
+        let iv = [0u8; 16];
+
+        let encrypted = encrypt_cbc(KEY, iv, message);
+        let decrypted = decrypt_cbc(KEY, iv, &encrypted).unwrap();
+
+        assert_eq!(decrypted, message);
+    }
+
+    #[test]
+    fn detect_ecb_flags_repeated_blocks() {
+        let message = b"YELLOW SUBMARINEYELLOW SUBMARINEYELLOW SUBMARINE";
+
+        let encrypted = encrypt_ecb(KEY, message);
+
+        assert_eq!(detect_ecb(&encrypted, 16), 2);
+    }
+}
@@ -0,0 +1,162 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::io::{from_base16, ToBase16};
+use crate::sha1::hmac_sha1;
+
+/// Serves a single `file`, accepting requests of the form
+/// `GET /?signature=<hex> HTTP/1.1` and checking the given hex-encoded
+/// signature against `hmac_sha1(key, file)`.
+///
+/// The check is deliberately insecure: it compares byte-by-byte and sleeps
+/// `delay` after every matching byte, which is exactly the kind of leak
+/// [`recover_mac`] exploits.
+pub struct InsecureCompareServer {
+    key: Vec<u8>,
+    file: Vec<u8>,
+    delay: Duration,
+}
+
+impl InsecureCompareServer {
+    pub fn new(key: Vec<u8>, file: Vec<u8>, delay: Duration) -> Self {
+        InsecureCompareServer { key, file, delay }
+    }
+
+    pub fn serve(&self, listener: &TcpListener) -> std::io::Result<()> {
+        for stream in listener.incoming() {
+            let stream = stream?;
+
+            // A client that only reads the first response byte (as
+            // `time_request` does, to capture a timing sample) drops the
+            // connection before we finish writing, which surfaces here as a
+            // broken-pipe/connection-reset error. That's an expected race
+            // for this kind of timing attack, not a reason to stop serving
+            // the rest of the connections.
+            let _ = self.handle_connection(stream);
+        }
+
+        Ok(())
+    }
+
+    fn handle_connection(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let request_line = BufReader::new(&mut stream)
+            .lines()
+            .next()
+            .transpose()?
+            .unwrap_or_default();
+
+        let signature = request_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|target| target.split("signature=").nth(1))
+            .and_then(|hex| from_base16(hex).ok());
+
+        let expected = hmac_sha1(&self.key, &self.file);
+        let valid = matches!(signature, Some(signature) if insecure_compare(&signature, &expected, self.delay));
+
+        let status = if valid {
+            "HTTP/1.1 200 OK"
+        } else {
+            "HTTP/1.1 500 Internal Server Error"
+        };
+        write!(stream, "{status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+    }
+}
+
+/// An insecure byte-by-byte comparison that leaks timing information: it
+/// returns as soon as it finds a mismatch, sleeping `delay` after every byte
+/// it confirms matches.
+fn insecure_compare(a: &[u8], b: &[u8], delay: Duration) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    for (x, y) in a.iter().zip(b) {
+        if x != y {
+            return false;
+        }
+        thread::sleep(delay);
+    }
+
+    true
+}
+
+fn time_request(addr: std::net::SocketAddr, signature: &[u8]) -> Duration {
+    let hex = format!("{}", ToBase16(signature));
+
+    let start = Instant::now();
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "GET /?signature={hex} HTTP/1.1\r\nConnection: close\r\n\r\n"
+    )
+    .unwrap();
+
+    // The server only writes its status line once it has finished comparing,
+    // so blocking on the first byte of the response is enough to time it.
+    let mut byte = [0u8; 1];
+    let _ = stream.read(&mut byte);
+
+    start.elapsed()
+}
+
+/// Recovers an `hmac_sha1`-style MAC of `mac_len` bytes one byte at a time by
+/// timing `addr`'s insecure comparison: for each position, every candidate
+/// byte is tried `samples` times and the one with the largest median
+/// response time is kept, since a correct byte makes the server sleep once
+/// more than an incorrect one before it can reject the guess. Averaging
+/// several samples and taking the median (rather than the mean) is what
+/// makes the signal survive ordinary network jitter.
+pub fn recover_mac(addr: std::net::SocketAddr, mac_len: usize, samples: usize) -> Vec<u8> {
+    let mut mac = vec![0u8; mac_len];
+
+    for i in 0..mac_len {
+        let mut best_byte = 0u8;
+        let mut best_median = Duration::ZERO;
+
+        for b in 0x00..=0xff {
+            mac[i] = b;
+
+            let mut durations: Vec<Duration> =
+                (0..samples).map(|_| time_request(addr, &mac)).collect();
+            durations.sort();
+            let median = durations[durations.len() / 2];
+
+            if median > best_median {
+                best_median = median;
+                best_byte = b;
+            }
+        }
+
+        mac[i] = best_byte;
+    }
+
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_recover_mac_via_timing() {
+        let key = b"request-forgery-key".to_vec();
+        let file = b"to be or not to be".to_vec();
+        let delay = Duration::from_millis(5);
+
+        let expected = hmac_sha1(&key, &file).to_vec();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = InsecureCompareServer::new(key, file, delay);
+        thread::spawn(move || server.serve(&listener));
+
+        let recovered = recover_mac(addr, expected.len(), 10);
+
+        assert_eq!(recovered, expected);
+    }
+}
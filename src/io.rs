@@ -1,7 +1,7 @@
 use std::fmt;
 
 pub fn from_base16(input: &str) -> Result<Vec<u8>, ()> {
-    let input = input.as_bytes();
+    let input: Vec<u8> = input.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
 
     if input.len() % 2 != 0 {
         return Err(());
@@ -28,36 +28,121 @@ pub fn from_base16(input: &str) -> Result<Vec<u8>, ()> {
     Ok(bytes)
 }
 
-pub fn from_base64(input: &str) -> Result<Vec<u8>, ()> {
-    let input = input.as_bytes();
+/// Which 62nd/63rd characters a base64 codec uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Base64Alphabet {
+    /// `+` and `/`, as used by RFC 4648 base64.
+    Standard,
+    /// `-` and `_`, safe to embed in a URL or filename.
+    UrlSafe,
+}
+
+impl Base64Alphabet {
+    fn decode_char(self, c: u8) -> u8 {
+        match (self, c) {
+            (_, b'A'..=b'Z') => c - b'A',
+            (_, b'a'..=b'z') => 26 + c - b'a',
+            (_, b'0'..=b'9') => 52 + c - b'0',
+            (Base64Alphabet::Standard, b'+') => 62,
+            (Base64Alphabet::Standard, b'/') => 63,
+            (Base64Alphabet::UrlSafe, b'-') => 62,
+            (Base64Alphabet::UrlSafe, b'_') => 63,
+            _ => 0xff,
+        }
+    }
+
+    fn encode_char(self, i: u8) -> u8 {
+        match (self, i) {
+            (_, 0..=25) => b'A' + i,
+            (_, 26..=51) => b'a' + i - 26,
+            (_, 52..=61) => b'0' + i - 52,
+            (Base64Alphabet::Standard, 62) => b'+',
+            (Base64Alphabet::Standard, 63) => b'/',
+            (Base64Alphabet::UrlSafe, 62) => b'-',
+            (Base64Alphabet::UrlSafe, 63) => b'_',
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Whether `=` padding is expected, allowed, or forbidden in encoded base64.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Base64Padding {
+    Required,
+    Optional,
+    None,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Base64Config {
+    pub alphabet: Base64Alphabet,
+    pub padding: Base64Padding,
+}
+
+impl Base64Config {
+    pub const STANDARD: Base64Config = Base64Config {
+        alphabet: Base64Alphabet::Standard,
+        padding: Base64Padding::Required,
+    };
+    pub const URL_SAFE: Base64Config = Base64Config {
+        alphabet: Base64Alphabet::UrlSafe,
+        padding: Base64Padding::Required,
+    };
+    pub const URL_SAFE_NO_PAD: Base64Config = Base64Config {
+        alphabet: Base64Alphabet::UrlSafe,
+        padding: Base64Padding::None,
+    };
+}
+
+impl Default for Base64Config {
+    fn default() -> Self {
+        Base64Config::STANDARD
+    }
+}
 
-    if input.len() % 4 != 0 {
+pub fn from_base64(input: &str, config: Base64Config) -> Result<Vec<u8>, ()> {
+    let symbols: Vec<u8> = input.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+
+    let pad_count = symbols.iter().rev().take_while(|&&b| b == b'=').count();
+    let data = &symbols[..symbols.len() - pad_count];
+
+    if data.contains(&b'=') {
         return Err(());
     }
 
-    let mut bytes = Vec::with_capacity(input.len() / 4);
+    let remainder = data.len() % 4;
+    if remainder == 1 {
+        return Err(());
+    }
+    let expected_pad_count = match remainder {
+        0 => 0,
+        2 => 2,
+        3 => 1,
+        _ => unreachable!(),
+    };
 
-    for cs in input.chunks(4) {
-        let cs: &[u8; 4] = cs.try_into().unwrap();
+    match config.padding {
+        Base64Padding::Required if pad_count != expected_pad_count => return Err(()),
+        Base64Padding::Optional if pad_count != 0 && pad_count != expected_pad_count => {
+            return Err(())
+        }
+        Base64Padding::None if pad_count != 0 => return Err(()),
+        _ => {}
+    }
 
-        let is = cs.map(|c| match c {
-            b'A'..=b'Z' => c - b'A',
-            b'a'..=b'z' => 26 + c - b'a',
-            b'0'..=b'9' => 52 + c - b'0',
-            b'+' => 62,
-            b'/' => 63,
-            b'=' => 0xfe,
-            _ => 0xff,
-        });
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
 
-        match is {
-            [i1 @ 0x00..=0x3f, i2 @ 0x00..=0x3f, i3 @ 0x00..=0x3f, i4 @ 0x00..=0x3f] => {
-                bytes.extend([i1 << 2 | i2 >> 4, i2 << 4 | i3 >> 2, i3 << 6 | i4])
-            }
-            [i1 @ 0x00..=0x3f, i2 @ 0x00..=0x3f, i3 @ 0x00..=0x3f, 0xfe] => {
-                bytes.extend([i1 << 2 | i2 >> 4, i2 << 4 | i3 >> 2])
-            }
-            [i1 @ 0x00..=0x3f, i2 @ 0x00..=0x3f, 0xfe, 0xfe] => bytes.extend([i1 << 2 | i2 >> 4]),
+    for cs in data.chunks(4) {
+        let is: Vec<u8> = cs.iter().map(|&c| config.alphabet.decode_char(c)).collect();
+
+        if is.iter().any(|&i| i > 0x3f) {
+            return Err(());
+        }
+
+        match is.as_slice() {
+            [i1, i2, i3, i4] => bytes.extend([i1 << 2 | i2 >> 4, i2 << 4 | i3 >> 2, i3 << 6 | i4]),
+            [i1, i2, i3] => bytes.extend([i1 << 2 | i2 >> 4, i2 << 4 | i3 >> 2]),
+            [i1, i2] => bytes.extend([i1 << 2 | i2 >> 4]),
             _ => return Err(()),
         }
     }
@@ -91,11 +176,12 @@ impl fmt::Display for ToBase16<'_> {
     }
 }
 
-pub struct ToBase64<'a>(pub &'a [u8]);
+pub struct ToBase64<'a>(pub &'a [u8], pub Base64Config);
 
 impl fmt::Display for ToBase64<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let bytes = self.0;
+        let config = self.1;
 
         let iter = bytes
             .chunks(3)
@@ -115,14 +201,11 @@ impl fmt::Display for ToBase64<'_> {
                 ],
                 _ => unreachable!(),
             })
-            .map(|i| match i {
-                0..=25 => b'A' + i,
-                26..=51 => b'a' + i - 26,
-                52..=61 => b'0' + i - 52,
-                62 => b'+',
-                63 => b'/',
+            .enumerate()
+            .filter(|&(_, i)| i != 0xff || config.padding != Base64Padding::None)
+            .map(|(_, i)| match i {
                 0xff => b'=',
-                _ => unreachable!(),
+                i => config.alphabet.encode_char(i),
             });
 
         for c in iter {
@@ -148,12 +231,55 @@ mod tests {
         assert_eq!(bytes, TEXT.as_bytes());
     }
 
+    #[test]
+    fn from_base16_skips_whitespace() {
+        let bytes = from_base16("49276d20\n6b696c6c\r\n696e6720796f757220627261696e20 6c696b65206120706f69736f6e6f7573206d757368726f6f6d").unwrap();
+        assert_eq!(bytes, TEXT.as_bytes());
+    }
+
     #[test]
     fn from_base64_works() {
-        let bytes = from_base64(BASE64).unwrap();
+        let bytes = from_base64(BASE64, Base64Config::STANDARD).unwrap();
         assert_eq!(bytes, TEXT.as_bytes());
     }
 
+    #[test]
+    fn from_base64_skips_whitespace() {
+        let wrapped = "SSdtIGtpbGxpbmcgeW91ciBi\ncmFpbiBsaWtlIGEgcG9pc29u\nb3VzIG11c2hyb29t\n";
+        let bytes = from_base64(wrapped, Base64Config::STANDARD).unwrap();
+        assert_eq!(bytes, TEXT.as_bytes());
+    }
+
+    #[test]
+    fn from_base64_accepts_url_safe_alphabet() {
+        // "+/8=" is the standard encoding of [0xfb, 0xff].
+        let url_safe = "+/8=".replace('+', "-").replace('/', "_");
+        let bytes = from_base64(&url_safe, Base64Config::URL_SAFE).unwrap();
+        assert_eq!(bytes, [0xfb, 0xff]);
+    }
+
+    #[test]
+    fn from_base64_accepts_missing_padding_when_optional() {
+        let unpadded = "aGVsbG8=".trim_end_matches('=');
+        let config = Base64Config {
+            padding: Base64Padding::Optional,
+            ..Base64Config::STANDARD
+        };
+
+        let bytes = from_base64(unpadded, config).unwrap();
+        assert_eq!(bytes, b"hello");
+    }
+
+    #[test]
+    fn from_base64_rejects_padding_when_required_but_missing() {
+        // BASE64/TEXT above are a multiple of 3 bytes long, so they never
+        // carry padding in the first place; this test needs a fixture whose
+        // encoding actually requires it.
+        let padded = "aGVsbG8=";
+        let unpadded = padded.trim_end_matches('=');
+        assert_eq!(from_base64(unpadded, Base64Config::STANDARD), Err(()));
+    }
+
     #[test]
     fn to_base16_works() {
         let bytes = TEXT.as_bytes();
@@ -163,6 +289,17 @@ mod tests {
     #[test]
     fn to_base64_works() {
         let bytes = TEXT.as_bytes();
-        assert_eq!(format!("{}", ToBase64(bytes)), BASE64);
+        assert_eq!(
+            format!("{}", ToBase64(bytes, Base64Config::STANDARD)),
+            BASE64,
+        );
+    }
+
+    #[test]
+    fn to_base64_omits_padding_when_configured() {
+        let bytes = b"hello";
+        let unpadded = format!("{}", ToBase64(bytes, Base64Config::URL_SAFE_NO_PAD));
+
+        assert_eq!(unpadded, "aGVsbG8=".trim_end_matches('='));
     }
 }
@@ -0,0 +1,7 @@
+pub mod aes;
+pub mod http;
+pub mod io;
+pub mod md4;
+pub mod random;
+pub mod sha1;
+pub mod xor;
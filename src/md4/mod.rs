@@ -0,0 +1,227 @@
+const BLOCK_SIZE: usize = 64;
+
+const H0: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+/// A streaming MD4 hasher.
+///
+/// [`Md4::from_state`] exposes the internal register state so a
+/// length-extension attack can resume hashing from a digest it did not
+/// itself produce; see [`length_extension`].
+pub struct Md4 {
+    h: [u32; 4],
+    len: u64,
+    buffer: Vec<u8>,
+}
+
+impl Md4 {
+    pub fn new() -> Self {
+        Md4 {
+            h: H0,
+            len: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Seeds a hasher as if it had already processed `processed_len` bytes
+    /// (a multiple of [`BLOCK_SIZE`]) ending in state `h`.
+    pub fn from_state(h: [u32; 4], processed_len: u64) -> Self {
+        Md4 {
+            h,
+            len: processed_len,
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+        self.len += data.len() as u64;
+
+        let mut chunks = self.buffer.chunks_exact(BLOCK_SIZE);
+        for chunk in &mut chunks {
+            process_block(&mut self.h, chunk.try_into().unwrap());
+        }
+
+        self.buffer = chunks.remainder().to_vec();
+    }
+
+    pub fn finalize(mut self) -> [u8; 16] {
+        let bit_len = self.len * 8;
+
+        self.buffer.push(0x80);
+        while self.buffer.len() % BLOCK_SIZE != 56 {
+            self.buffer.push(0);
+        }
+        self.buffer.extend(bit_len.to_le_bytes());
+
+        for chunk in self.buffer.chunks_exact(BLOCK_SIZE) {
+            process_block(&mut self.h, chunk.try_into().unwrap());
+        }
+
+        let mut digest = [0u8; 16];
+        for (word, bytes) in self.h.iter().zip(digest.chunks_exact_mut(4)) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+
+        digest
+    }
+}
+
+impl Default for Md4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const ROUND2_K: u32 = 0x5a827999;
+const ROUND3_K: u32 = 0x6ed9eba1;
+
+#[rustfmt::skip]
+const ROUND2_ORDER: [usize; 16] = [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15];
+#[rustfmt::skip]
+const ROUND3_ORDER: [usize; 16] = [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15];
+
+fn process_block(h: &mut [u32; 4], block: &[u8; 64]) {
+    let mut x = [0u32; 16];
+    for (i, word) in x.iter_mut().enumerate() {
+        *word = u32::from_le_bytes(block[4 * i..4 * i + 4].try_into().unwrap());
+    }
+
+    let [mut a, mut b, mut c, mut d] = *h;
+
+    for (i, &s) in [3, 7, 11, 19].iter().cycle().take(16).enumerate() {
+        let f = (b & c) | (!b & d);
+        let temp = a.wrapping_add(f).wrapping_add(x[i]).rotate_left(s);
+        a = d;
+        d = c;
+        c = b;
+        b = temp;
+    }
+
+    for (i, &s) in [3, 5, 9, 13].iter().cycle().take(16).enumerate() {
+        let k = ROUND2_ORDER[i];
+        let g = (b & c) | (b & d) | (c & d);
+        let temp = a
+            .wrapping_add(g)
+            .wrapping_add(x[k])
+            .wrapping_add(ROUND2_K)
+            .rotate_left(s);
+        a = d;
+        d = c;
+        c = b;
+        b = temp;
+    }
+
+    for (i, &s) in [3, 9, 11, 15].iter().cycle().take(16).enumerate() {
+        let k = ROUND3_ORDER[i];
+        let hh = b ^ c ^ d;
+        let temp = a
+            .wrapping_add(hh)
+            .wrapping_add(x[k])
+            .wrapping_add(ROUND3_K)
+            .rotate_left(s);
+        a = d;
+        d = c;
+        c = b;
+        b = temp;
+    }
+
+    h[0] = h[0].wrapping_add(a);
+    h[1] = h[1].wrapping_add(b);
+    h[2] = h[2].wrapping_add(c);
+    h[3] = h[3].wrapping_add(d);
+}
+
+pub fn hash(data: &[u8]) -> [u8; 16] {
+    let mut hasher = Md4::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// The padding MD4 appends to a message of `message_len` bytes before
+/// hashing it, i.e. everything after the raw message and before the digest
+/// is taken.
+pub fn md_padding(message_len: u64) -> Vec<u8> {
+    let bit_len = message_len * 8;
+
+    let mut padding = vec![0x80];
+    while (message_len as usize + padding.len()) % BLOCK_SIZE != 56 {
+        padding.push(0);
+    }
+    padding.extend(bit_len.to_le_bytes());
+
+    padding
+}
+
+/// Forges `glue_padding` and `H(secret || original || glue_padding || suffix)`
+/// given only `original_digest = H(secret || original)` and
+/// `original_len = secret.len() + original.len()`, without knowing `secret`.
+pub fn length_extension(
+    original_digest: [u8; 16],
+    original_len: u64,
+    suffix: &[u8],
+) -> (Vec<u8>, [u8; 16]) {
+    let mut h = [0u32; 4];
+    for (word, bytes) in h.iter_mut().zip(original_digest.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+
+    let glue_padding = md_padding(original_len);
+    let processed_len = original_len + glue_padding.len() as u64;
+
+    let mut hasher = Md4::from_state(h, processed_len);
+    hasher.update(suffix);
+
+    (glue_padding, hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::io::ToBase16;
+
+    #[test]
+    fn hashes_empty_string() {
+        assert_eq!(
+            format!("{}", ToBase16(&hash(b""))),
+            "31d6cfe0d16ae931b73c59d7e0c089c0",
+        );
+    }
+
+    #[test]
+    fn hashes_abc() {
+        assert_eq!(
+            format!("{}", ToBase16(&hash(b"abc"))),
+            "a448017aaf21d8525fc10ae87aa6729d",
+        );
+    }
+
+    #[test]
+    fn hashes_across_multiple_updates() {
+        let mut hasher = Md4::new();
+        hasher.update(b"ab");
+        hasher.update(b"c");
+
+        assert_eq!(hasher.finalize(), hash(b"abc"));
+    }
+
+    #[test]
+    fn can_forge_length_extension() {
+        let key = b"supersecretkey!!";
+        let original = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+        let suffix = b";admin=true";
+
+        let mut message = key.to_vec();
+        message.extend_from_slice(original);
+        let original_digest = hash(&message);
+
+        let (glue_padding, forged_digest) =
+            length_extension(original_digest, message.len() as u64, suffix);
+
+        let mut forged_message = message.clone();
+        forged_message.extend_from_slice(&glue_padding);
+        forged_message.extend_from_slice(suffix);
+
+        assert_eq!(hash(&forged_message), forged_digest);
+    }
+}
@@ -0,0 +1,134 @@
+const N: usize = 624;
+const M: usize = 397;
+
+const MATRIX_A: u32 = 0x9908b0df;
+const UPPER_MASK: u32 = 0x80000000;
+const LOWER_MASK: u32 = 0x7fffffff;
+
+pub struct Mt19937 {
+    state: [u32; N],
+    index: usize,
+}
+
+impl Mt19937 {
+    pub fn seed(seed: u32) -> Self {
+        let mut state = [0u32; N];
+        state[0] = seed;
+
+        for i in 1..N {
+            state[i] = 1812433253u32
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+
+        let mut mt = Mt19937 { state, index: N };
+        mt.generate();
+
+        mt
+    }
+
+    /// Reconstructs a generator's internal state directly from 624 consecutive
+    /// untempered outputs, as produced by [`untemper`].
+    pub fn from_state(state: [u32; N]) -> Self {
+        Mt19937 { state, index: N }
+    }
+
+    fn generate(&mut self) {
+        for i in 0..N {
+            let x = (self.state[i] & UPPER_MASK) | (self.state[(i + 1) % N] & LOWER_MASK);
+            let mut x_a = x >> 1;
+
+            if x & 1 != 0 {
+                x_a ^= MATRIX_A;
+            }
+
+            self.state[i] = self.state[(i + M) % N] ^ x_a;
+        }
+
+        self.index = 0;
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        if self.index >= N {
+            self.generate();
+        }
+
+        let y = self.state[self.index];
+        self.index += 1;
+
+        temper(y)
+    }
+}
+
+fn temper(y: u32) -> u32 {
+    let y = y ^ (y >> 11);
+    let y = y ^ ((y << 7) & 0x9d2c5680);
+    let y = y ^ ((y << 15) & 0xefc60000);
+    y ^ (y >> 18)
+}
+
+// Each tempering step XORs a value into `x` with a mask derived from a shift
+// of `x` itself, which makes it invertible: repeatedly re-deriving the mask
+// from our best guess converges on the original `x` within 32/shift rounds.
+fn undo_shift_right_xor(y: u32, shift: u32) -> u32 {
+    let mut x = y;
+    for _ in 0..32 {
+        x = y ^ (x >> shift);
+    }
+    x
+}
+
+fn undo_shift_left_xor_mask(y: u32, shift: u32, mask: u32) -> u32 {
+    let mut x = y;
+    for _ in 0..32 {
+        x = y ^ ((x << shift) & mask);
+    }
+    x
+}
+
+/// Inverts [`temper`] bit-by-bit, recovering the raw state word that produced
+/// a given output.
+pub fn untemper(y: u32) -> u32 {
+    let y = undo_shift_right_xor(y, 18);
+    let y = undo_shift_left_xor_mask(y, 15, 0xefc60000);
+    let y = undo_shift_left_xor_mask(y, 7, 0x9d2c5680);
+    undo_shift_right_xor(y, 11)
+}
+
+/// Rebuilds a generator that predicts all future output from 624 consecutive
+/// observed outputs, by untempering each one back into the internal state.
+pub fn clone_from_outputs(outputs: &[u32; N]) -> Mt19937 {
+    let mut state = [0u32; N];
+
+    for (s, &o) in state.iter_mut().zip(outputs) {
+        *s = untemper(o);
+    }
+
+    Mt19937::from_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untemper_inverts_temper() {
+        let ys = [0u32, 1, 0xdeadbeef, 0xffffffff, 1812433253, 42];
+
+        for y in ys {
+            assert_eq!(untemper(temper(y)), y);
+        }
+    }
+
+    #[test]
+    fn can_clone_generator_from_outputs() {
+        let mut original = Mt19937::seed(0x12345678);
+
+        let outputs: [u32; N] = std::array::from_fn(|_| original.next_u32());
+        let mut clone = clone_from_outputs(&outputs);
+
+        for _ in 0..1000 {
+            assert_eq!(clone.next_u32(), original.next_u32());
+        }
+    }
+}
@@ -0,0 +1,233 @@
+const BLOCK_SIZE: usize = 64;
+
+const H0: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// A streaming SHA-1 hasher.
+///
+/// [`Sha1::from_state`] exposes the internal register state so a
+/// length-extension attack can resume hashing from a digest it did not
+/// itself produce; see [`length_extension`].
+pub struct Sha1 {
+    h: [u32; 5],
+    len: u64,
+    buffer: Vec<u8>,
+}
+
+impl Sha1 {
+    pub fn new() -> Self {
+        Sha1 {
+            h: H0,
+            len: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Seeds a hasher as if it had already processed `processed_len` bytes
+    /// (a multiple of [`BLOCK_SIZE`]) ending in state `h`.
+    pub fn from_state(h: [u32; 5], processed_len: u64) -> Self {
+        Sha1 {
+            h,
+            len: processed_len,
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+        self.len += data.len() as u64;
+
+        let mut chunks = self.buffer.chunks_exact(BLOCK_SIZE);
+        for chunk in &mut chunks {
+            process_block(&mut self.h, chunk.try_into().unwrap());
+        }
+
+        self.buffer = chunks.remainder().to_vec();
+    }
+
+    pub fn finalize(mut self) -> [u8; 20] {
+        let bit_len = self.len * 8;
+
+        self.buffer.push(0x80);
+        while self.buffer.len() % BLOCK_SIZE != 56 {
+            self.buffer.push(0);
+        }
+        self.buffer.extend(bit_len.to_be_bytes());
+
+        for chunk in self.buffer.chunks_exact(BLOCK_SIZE) {
+            process_block(&mut self.h, chunk.try_into().unwrap());
+        }
+
+        let mut digest = [0u8; 20];
+        for (word, bytes) in self.h.iter().zip(digest.chunks_exact_mut(4)) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+
+        digest
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn process_block(h: &mut [u32; 5], block: &[u8; 64]) {
+    let mut w = [0u32; 80];
+
+    for (i, word) in w.iter_mut().take(16).enumerate() {
+        *word = u32::from_be_bytes(block[4 * i..4 * i + 4].try_into().unwrap());
+    }
+
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *h;
+
+    for (i, &wi) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..=19 => ((b & c) | (!b & d), 0x5a827999),
+            20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(wi);
+
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    h[0] = h[0].wrapping_add(a);
+    h[1] = h[1].wrapping_add(b);
+    h[2] = h[2].wrapping_add(c);
+    h[3] = h[3].wrapping_add(d);
+    h[4] = h[4].wrapping_add(e);
+}
+
+pub fn hash(data: &[u8]) -> [u8; 20] {
+    let mut hasher = Sha1::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// The padding SHA-1 appends to a message of `message_len` bytes before
+/// hashing it, i.e. everything after the raw message and before the digest
+/// is taken.
+pub fn md_padding(message_len: u64) -> Vec<u8> {
+    let bit_len = message_len * 8;
+
+    let mut padding = vec![0x80];
+    while (message_len as usize + padding.len()) % BLOCK_SIZE != 56 {
+        padding.push(0);
+    }
+    padding.extend(bit_len.to_be_bytes());
+
+    padding
+}
+
+/// Forges `glue_padding` and `H(secret || original || glue_padding || suffix)`
+/// given only `original_digest = H(secret || original)` and
+/// `original_len = secret.len() + original.len()`, without knowing `secret`.
+pub fn length_extension(
+    original_digest: [u8; 20],
+    original_len: u64,
+    suffix: &[u8],
+) -> (Vec<u8>, [u8; 20]) {
+    let mut h = [0u32; 5];
+    for (word, bytes) in h.iter_mut().zip(original_digest.chunks_exact(4)) {
+        *word = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+
+    let glue_padding = md_padding(original_len);
+    let processed_len = original_len + glue_padding.len() as u64;
+
+    let mut hasher = Sha1::from_state(h, processed_len);
+    hasher.update(suffix);
+
+    (glue_padding, hasher.finalize())
+}
+
+pub fn hmac_sha1(key: &[u8], message: &[u8]) -> [u8; 20] {
+    let mut block_key = [0u8; 64];
+
+    if key.len() > 64 {
+        block_key[..20].copy_from_slice(&hash(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let ipad: Vec<u8> = block_key.iter().map(|b| b ^ 0x36).collect();
+    let opad: Vec<u8> = block_key.iter().map(|b| b ^ 0x5c).collect();
+
+    let mut inner = Sha1::new();
+    inner.update(&ipad);
+    inner.update(message);
+    let inner_digest = inner.finalize();
+
+    let mut outer = Sha1::new();
+    outer.update(&opad);
+    outer.update(&inner_digest);
+    outer.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::io::ToBase16;
+
+    #[test]
+    fn hashes_empty_string() {
+        assert_eq!(
+            format!("{}", ToBase16(&hash(b""))),
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+        );
+    }
+
+    #[test]
+    fn hashes_abc() {
+        assert_eq!(
+            format!("{}", ToBase16(&hash(b"abc"))),
+            "a9993e364706816aba3e25717850c26c9cd0d89d",
+        );
+    }
+
+    #[test]
+    fn hashes_across_multiple_updates() {
+        let mut hasher = Sha1::new();
+        hasher.update(b"ab");
+        hasher.update(b"c");
+
+        assert_eq!(hasher.finalize(), hash(b"abc"));
+    }
+
+    #[test]
+    fn can_forge_length_extension() {
+        let key = b"supersecretkey!!";
+        let original = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+        let suffix = b";admin=true";
+
+        let mut message = key.to_vec();
+        message.extend_from_slice(original);
+        let original_digest = hash(&message);
+
+        let (glue_padding, forged_digest) =
+            length_extension(original_digest, message.len() as u64, suffix);
+
+        let mut forged_message = message.clone();
+        forged_message.extend_from_slice(&glue_padding);
+        forged_message.extend_from_slice(suffix);
+
+        assert_eq!(hash(&forged_message), forged_digest);
+    }
+}
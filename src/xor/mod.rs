@@ -37,29 +37,36 @@ const DICTIONARY: &[(u8, f64)] = &[
     (b' ', 0.1918182),
 ];
 
-// https://crypto.stackexchange.com/a/56477
-pub fn englishness(bytes: impl Iterator<Item = u8>) -> f64 {
-    let mut counts = std::collections::HashMap::<u8, usize>::new();
-    let mut total = 0;
-
-    for b in bytes {
-        *counts.entry(b.to_ascii_uppercase()).or_insert(0) += 1;
-        total += 1;
+// Unlisted bytes still occur in English text (punctuation, digits, etc.;
+// lowercase letters are folded onto the listed uppercase ones), so give them
+// a small floor probability rather than scoring them as impossible.
+const UNLISTED_PROBABILITY: f64 = 1e-6;
+
+// Binary garbage decodes to bytes outside printable ASCII far more often than
+// English prose does, so a hard penalty per such byte rejects it outright
+// even when the few printable bytes it does contain happen to score well.
+const NON_PRINTABLE_PENALTY: f64 = -100.0;
+
+fn byte_log_probability(b: u8) -> f64 {
+    if !b.is_ascii_graphic() && b != b' ' && b != b'\t' && b != b'\n' && b != b'\r' {
+        return NON_PRINTABLE_PENALTY;
     }
 
-    let mut bc = 0.0;
+    let p = DICTIONARY
+        .iter()
+        .find(|(db, _)| *db == b.to_ascii_uppercase())
+        .map_or(UNLISTED_PROBABILITY, |(_, f)| *f);
 
-    for (b, f) in DICTIONARY {
-        let count = *counts.get(b).unwrap_or(&0);
-        bc += ((count as f64) / (total as f64) * f).sqrt();
-    }
+    p.ln()
+}
 
-    bc
+pub fn englishness(bytes: impl Iterator<Item = u8>) -> f64 {
+    bytes.map(byte_log_probability).sum()
 }
 
 pub fn find_single_byte_xor_key(bytes: impl Iterator<Item = u8> + Clone) -> (u8, f64) {
     let mut best_b = 0;
-    let mut best_e = 0.0;
+    let mut best_e = f64::NEG_INFINITY;
 
     for b in 0x00..=0xff {
         let decoded = xor(bytes.clone(), iter::repeat(b));
@@ -74,6 +81,26 @@ pub fn find_single_byte_xor_key(bytes: impl Iterator<Item = u8> + Clone) -> (u8,
     (best_b, best_e)
 }
 
+/// Like [`find_single_byte_xor_key`], but returns the top `n` keys by score
+/// instead of committing to a single best guess, so callers can disambiguate
+/// near-ties on short ciphertexts.
+pub fn find_single_byte_xor_candidates(
+    bytes: impl Iterator<Item = u8> + Clone,
+    n: usize,
+) -> Vec<(u8, f64)> {
+    let mut candidates: Vec<(u8, f64)> = (0x00..=0xff)
+        .map(|b| {
+            let decoded = xor(bytes.clone(), iter::repeat(b));
+            (b, englishness(decoded))
+        })
+        .collect();
+
+    candidates.sort_by(|(_, e1), (_, e2)| e2.partial_cmp(e1).unwrap());
+    candidates.truncate(n);
+
+    candidates
+}
+
 pub fn hamming_distance(
     bytes1: impl Iterator<Item = u8>,
     bytes2: impl Iterator<Item = u8>,
@@ -160,13 +187,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn candidates_include_the_best_key() {
+        let message =
+            from_base16("1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736")
+                .unwrap();
+
+        let (best_key, _) = find_single_byte_xor_key(message.iter().copied());
+        let candidates = find_single_byte_xor_candidates(message.iter().copied(), 5);
+
+        assert_eq!(candidates.len(), 5);
+        assert!(candidates.iter().any(|(key, _)| *key == best_key));
+    }
+
     #[test]
     fn can_find_single_byte_xor_string() {
         let file = include_str!("single_byte_xor.txt");
 
         let mut message = Vec::new();
         let mut best_key = 0x00;
-        let mut best_englishness = 0.0;
+        let mut best_englishness = f64::NEG_INFINITY;
 
         for line in file.lines() {
             let bytes = from_base16(line).unwrap();
@@ -214,8 +254,8 @@ mod tests {
 
     #[test]
     fn find_repeating_key_xor_key_works() {
-        let message: String = include_str!("repeating_key_xor.txt").lines().collect();
-        let message = from_base64(&message).unwrap();
+        let message = include_str!("repeating_key_xor.txt");
+        let message = from_base64(message, Base64Config::STANDARD).unwrap();
 
         let key = find_repeating_key_xor_key(message.iter().copied());
 